@@ -1,11 +1,24 @@
+use crate::profile::Profile;
 use crate::serialization::{FsCacheable, FsLoadable};
+use crate::text::normalize_tweet_text;
 use crate::twitter::json_types::{LikedTweets, TwitLikeResponse, UserIdLookup};
 use std::path::{Path, PathBuf};
 use std::{env, fs, io};
 use std::{error::Error, fmt};
 
+/// Fallback cache directory name, relative to the current working directory,
+/// used when neither the env var override nor the platform data directory
+/// are available.
 const CACHE_DIRNAME: &str = ".cache";
 
+/// Name of the subdirectory created under the platform data directory
+/// (e.g. `~/.local/share` on Linux, `~/Library/Application Support` on macOS).
+const APP_DIRNAME: &str = "twitter-export-likes";
+
+/// Environment variable that, when set, overrides the resolved cache
+/// directory entirely.
+const CACHE_DIR_ENV_VAR: &str = "TWITTER_EXPORT_DIR";
+
 #[derive(Debug)]
 pub enum CacheLoadError {
     NoTweets(String),
@@ -19,20 +32,20 @@ impl fmt::Display for CacheLoadError {
 }
 
 /// Attempts to load a UserIdLookup from cache or else returns an error.
-pub fn load_user_lookup() -> Result<UserIdLookup, Box<dyn Error>> {
+pub fn load_user_lookup(profile: &Profile) -> Result<UserIdLookup, Box<dyn Error>> {
     // If it exists, load the users lookup from cache. Caching this data means
     // that we don't have to go back to the API repeatedly for user info between runs.
-    let cache_directory = get_cache_directory_path()?;
-    fs::create_dir_all(&cache_directory)?;
-    let full_path = UserIdLookup::fs_full_path()?;
+    // get_cache_directory_path() ensures the directory exists as a side effect.
+    get_cache_directory_path(profile)?;
+    let full_path = UserIdLookup::fs_full_path(profile)?;
     let user_id_lkup = UserIdLookup::load(&full_path)?;
     Ok(user_id_lkup)
 }
 
 /// Attempts to load a UserIdLookup from cache, and failing that returns a new
 /// empty object.
-pub fn try_load_user_lookup() -> UserIdLookup {
-    match load_user_lookup() {
+pub fn try_load_user_lookup(profile: &Profile) -> UserIdLookup {
+    match load_user_lookup(profile) {
         Ok(uil) => uil,
         Err(err) => {
             println!("try_load_user_lookup: error {:?}", err);
@@ -41,19 +54,22 @@ pub fn try_load_user_lookup() -> UserIdLookup {
     }
 }
 
-/// Loads tweets previously cached in the .cache directory into a single object.
-/// Returns an error if the attempt to load fails.
+/// Loads tweets previously cached in the profile's cache directory into a
+/// single object. Returns an error if the attempt to load fails.
 ///
 /// # Arguments
 ///
+/// * `profile` - The profile whose cache directory should be searched.
 /// * `username` - A string slice representing the twitter username (not user id).
-pub fn load_all_liked_tweets_from_cache(username: &str) -> Result<LikedTweets, Box<dyn Error>> {
+pub fn load_all_liked_tweets_from_cache(
+    profile: &Profile,
+    username: &str,
+) -> Result<LikedTweets, Box<dyn Error>> {
     // From the cache directory, find all cached JSON files with liked tweets.
-    // TODO: allow the cache directory to be configurable.
-    let cache_directory = env::current_dir()?.join(CACHE_DIRNAME);
+    let cache_directory = get_cache_directory_path(profile)?;
     let paths = fs::read_dir(cache_directory)?;
     let mut liked_tweets = LikedTweets::new();
-    let user_id_lkup = UserIdLookup::load_default()?;
+    let user_id_lkup = UserIdLookup::load_default(profile)?;
 
     for path in paths {
         let path = path.unwrap().path();
@@ -77,6 +93,10 @@ pub fn load_all_liked_tweets_from_cache(username: &str) -> Result<LikedTweets, B
                             None => panic!("Expected user data for {}", &datum.author_id),
                         };
                         datum.user = Some(user.clone());
+                        // Keep the raw API text intact, but also compute a
+                        // reader-friendly version with retweet/truncation
+                        // resolved, entities decoded, and t.co links expanded.
+                        datum.normalized_text = Some(normalize_tweet_text(&datum));
                         liked_tweets.tweets.push(datum);
                     }
                 }
@@ -101,13 +121,40 @@ pub fn load_all_liked_tweets_from_cache(username: &str) -> Result<LikedTweets, B
 /// # Errors
 ///
 /// This function will return an error if no cache filesystem path is available.
-pub fn get_cache_file_path(filename: &str) -> std::io::Result<PathBuf> {
-    Ok(get_cache_directory_path()?.join(filename))
+pub fn get_cache_file_path(profile: &Profile, filename: &str) -> std::io::Result<PathBuf> {
+    Ok(get_cache_directory_path(profile)?.join(filename))
+}
+
+/// Resolves, creates (if missing), and returns the filesystem path for the
+/// given profile's cache directory. Every other function in this module
+/// routes through this resolver so the cache location stays consistent and
+/// overridable.
+///
+/// Resolution order for the base directory (beneath which the profile is
+/// nested):
+///
+/// 1. `TWITTER_EXPORT_DIR` environment variable, if set.
+/// 2. The platform data directory (via [`dirs::data_dir`]), nested under
+///    `APP_DIRNAME`.
+/// 3. `CACHE_DIRNAME` (.cache) in the current working directory.
+pub fn get_cache_directory_path(profile: &Profile) -> io::Result<PathBuf> {
+    let cache_directory = resolve_cache_directory()?.join(profile.name());
+    fs::create_dir_all(&cache_directory)?;
+    cache_directory.canonicalize()
 }
 
-/// Gets the filesystem path for the cache directory (currently, this is set to
-/// `CACHE_DIRNAME` (.cache) in the current working directory).
-pub fn get_cache_directory_path() -> io::Result<PathBuf> {
+/// Picks the base cache directory without creating or canonicalizing it.
+/// Separated from [`get_cache_directory_path`] so the resolution order can
+/// be reasoned about (and tested) independently of filesystem side effects.
+fn resolve_cache_directory() -> io::Result<PathBuf> {
+    if let Ok(dir) = env::var(CACHE_DIR_ENV_VAR) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    if let Some(data_dir) = dirs::data_dir() {
+        return Ok(data_dir.join(APP_DIRNAME));
+    }
+
     Ok(env::current_dir()?.join(CACHE_DIRNAME))
 }
 
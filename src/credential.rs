@@ -0,0 +1,92 @@
+use crate::cache::get_cache_file_path;
+use crate::profile::Profile;
+use crate::serialization::{FsCacheable, FsLoadable};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CREDENTIALS_FILENAME: &str = "credentials.json";
+
+/// An OAuth 1.0a key/secret pair. Used both for Twitter's consumer
+/// (app-level) credentials and for a user's access token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Credential {
+    pub key: String,
+    pub secret: String,
+}
+
+impl Credential {
+    pub fn new(key: &str, secret: &str) -> Self {
+        Credential {
+            key: key.to_string(),
+            secret: secret.to_string(),
+        }
+    }
+}
+
+/// A profile's cached user access credential, persisted so the OAuth PIN
+/// flow only has to run once per profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileCredential {
+    pub access_token: Credential,
+}
+
+impl ProfileCredential {
+    pub fn new(access_token: Credential) -> Self {
+        ProfileCredential { access_token }
+    }
+
+    /// Gets the filesystem path for this profile's cached credential.
+    pub fn fs_full_path(profile: &Profile) -> std::io::Result<PathBuf> {
+        get_cache_file_path(profile, CREDENTIALS_FILENAME)
+    }
+}
+
+impl FsCacheable<ProfileCredential> for ProfileCredential {
+    fn cache(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)?;
+        restrict_permissions(path)?;
+        Ok(())
+    }
+}
+
+/// Restricts a cached credential file to owner-only read/write (`0600`).
+/// Unlike the lower-sensitivity `UserIdLookup`/`ExportState` caches, this
+/// file holds a live OAuth access token and secret, so it shouldn't be left
+/// at the default, group/world-readable permissions.
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> Result<(), Box<dyn Error>> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) -> Result<(), Box<dyn Error>> {
+    Ok(())
+}
+
+impl FsLoadable<ProfileCredential> for ProfileCredential {
+    fn load(path: &Path) -> Result<ProfileCredential, Box<dyn Error>> {
+        let json = fs::read_to_string(path)?;
+        let credential = serde_json::from_str(&json)?;
+        Ok(credential)
+    }
+}
+
+/// Attempts to load a cached credential for the profile, returning `None`
+/// if the profile hasn't completed the OAuth PIN flow yet, mirroring how
+/// `try_load_user_lookup` falls back to an empty/absent value while still
+/// logging what went wrong.
+pub fn try_load_credential(profile: &Profile) -> Option<ProfileCredential> {
+    let full_path = ProfileCredential::fs_full_path(profile).ok()?;
+    match ProfileCredential::load(&full_path) {
+        Ok(credential) => Some(credential),
+        Err(err) => {
+            println!("try_load_credential: error {:?}", err);
+            None
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::cache::get_cache_file_path;
+use crate::profile::Profile;
+use crate::serialization::{FsCacheable, FsLoadable};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Tracks how far a previous export got for a given username: the
+/// newest/oldest liked-tweet IDs seen so far and the last pagination token
+/// returned by the API. Lets a repeat export fetch only what's newer/older
+/// than what's already cached instead of re-pulling everything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportState {
+    pub username: String,
+    pub newest_id: Option<String>,
+    pub oldest_id: Option<String>,
+    pub pagination_token: Option<String>,
+}
+
+impl ExportState {
+    pub fn new(username: &str) -> Self {
+        ExportState {
+            username: username.to_string(),
+            newest_id: None,
+            oldest_id: None,
+            pagination_token: None,
+        }
+    }
+
+    /// Gets the filesystem path for this username's cached export state.
+    pub fn fs_full_path(profile: &Profile, username: &str) -> std::io::Result<PathBuf> {
+        get_cache_file_path(profile, &format!("export-state-{username}.json"))
+    }
+
+    /// Records the bounds of a freshly-fetched API page, widening the known
+    /// newest/oldest IDs and remembering where pagination should resume.
+    ///
+    /// `pagination_token` should be `Some(next_token)` when the API reports
+    /// more pages to fetch. `None` leaves any stored token untouched, so a
+    /// page without a token can't accidentally wipe a valid resume cursor;
+    /// once pagination has ended, call [`ExportState::clear_pagination_token`]
+    /// to drop the stored cursor.
+    pub fn record_page(
+        &mut self,
+        newest_id: Option<&str>,
+        oldest_id: Option<&str>,
+        pagination_token: Option<&str>,
+    ) {
+        if let Some(newest_id) = newest_id {
+            if self.newest_id.is_none() || id_lt(self.newest_id.as_deref().unwrap(), newest_id) {
+                self.newest_id = Some(newest_id.to_string());
+            }
+        }
+        if let Some(oldest_id) = oldest_id {
+            if self.oldest_id.is_none() || id_lt(oldest_id, self.oldest_id.as_deref().unwrap()) {
+                self.oldest_id = Some(oldest_id.to_string());
+            }
+        }
+        if let Some(pagination_token) = pagination_token {
+            self.pagination_token = Some(pagination_token.to_string());
+        }
+    }
+
+    /// Marks pagination as complete for this username, clearing any stored
+    /// resume cursor so the next export starts a fresh pull from the newest
+    /// tweets.
+    pub fn clear_pagination_token(&mut self) {
+        self.pagination_token = None;
+    }
+}
+
+/// Compares two tweet (snowflake) IDs numerically rather than
+/// lexicographically. IDs are numeric strings of varying digit length —
+/// e.g. pre-snowflake 10-digit IDs vs. current 19-digit IDs — so a byte
+/// comparison gets boundaries like `"9999999999"` vs. `"10000000000"`
+/// backwards. Falls back to comparing by length then lexicographically if
+/// either ID somehow isn't a valid `u64`.
+fn id_lt(a: &str, b: &str) -> bool {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a < b,
+        _ => (a.len(), a) < (b.len(), b),
+    }
+}
+
+impl FsCacheable<ExportState> for ExportState {
+    fn cache(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+impl FsLoadable<ExportState> for ExportState {
+    fn load(path: &Path) -> Result<ExportState, Box<dyn Error>> {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+}
+
+/// Attempts to load the cached export state for `username`.
+pub fn load_export_state(profile: &Profile, username: &str) -> Result<ExportState, Box<dyn Error>> {
+    let full_path = ExportState::fs_full_path(profile, username)?;
+    ExportState::load(&full_path)
+}
+
+/// Loads the cached export state for `username`, falling back to a fresh,
+/// empty state if none has been cached yet (e.g. this is the first export
+/// for that user), mirroring how `try_load_user_lookup` logs the load error
+/// and falls back to an empty object.
+pub fn try_load_export_state(profile: &Profile, username: &str) -> ExportState {
+    match load_export_state(profile, username) {
+        Ok(state) => state,
+        Err(err) => {
+            println!("try_load_export_state: error {:?}", err);
+            ExportState::new(username)
+        }
+    }
+}
+
+/// Returns the pagination token a resumed export for `username` should pass
+/// to the API, so pages already fetched in a previous run aren't re-pulled.
+/// `None` means either there's no prior export state, or the prior export
+/// ran to completion and a fresh pull should start from the newest tweets.
+pub fn resume_pagination_token(profile: &Profile, username: &str) -> Option<String> {
+    try_load_export_state(profile, username).pagination_token
+}
@@ -0,0 +1,200 @@
+use crate::cache::write_cache;
+use crate::credential::{try_load_credential, Credential, ProfileCredential};
+use crate::profile::Profile;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const REQUEST_TOKEN_URL: &str = "https://api.twitter.com/oauth/request_token";
+const AUTHORIZE_URL: &str = "https://api.twitter.com/oauth/authorize";
+const ACCESS_TOKEN_URL: &str = "https://api.twitter.com/oauth/access_token";
+
+/// Loads the cached credential for `profile` if one exists, otherwise runs
+/// the three-legged, PIN-based OAuth flow to acquire and cache a new one.
+pub fn load_or_authenticate(
+    profile: &Profile,
+    consumer: &Credential,
+) -> Result<ProfileCredential, Box<dyn Error>> {
+    match try_load_credential(profile) {
+        Some(credential) => Ok(credential),
+        None => authenticate(profile, consumer),
+    }
+}
+
+/// Runs Twitter's three-legged, PIN-based OAuth 1.0a flow and caches the
+/// resulting user access token for `profile`.
+///
+/// 1. Request a temporary request token.
+/// 2. Print the authorization URL for the user to open and approve.
+/// 3. Read back the PIN Twitter shows the user.
+/// 4. Exchange the PIN for a durable access token + secret.
+pub fn authenticate(
+    profile: &Profile,
+    consumer: &Credential,
+) -> Result<ProfileCredential, Box<dyn Error>> {
+    let request_token = fetch_request_token(consumer)?;
+
+    println!("Please open the following URL, approve access, and enter the PIN Twitter shows you:");
+    println!("{}?oauth_token={}", AUTHORIZE_URL, request_token.key);
+
+    let pin = read_pin()?;
+    let access_token = fetch_access_token(consumer, &request_token, &pin)?;
+
+    let profile_credential = ProfileCredential::new(access_token);
+    let full_path = ProfileCredential::fs_full_path(profile)?;
+    write_cache(&profile_credential, &full_path)?;
+    Ok(profile_credential)
+}
+
+fn read_pin() -> io::Result<String> {
+    print!("PIN: ");
+    io::stdout().flush()?;
+    let mut pin = String::new();
+    io::stdin().read_line(&mut pin)?;
+    Ok(pin.trim().to_string())
+}
+
+fn fetch_request_token(consumer: &Credential) -> Result<Credential, Box<dyn Error>> {
+    let body = oauth1_post(
+        REQUEST_TOKEN_URL,
+        consumer,
+        None,
+        &[("oauth_callback", "oob")],
+    )?;
+    parse_token_response(&body)
+}
+
+fn fetch_access_token(
+    consumer: &Credential,
+    request_token: &Credential,
+    pin: &str,
+) -> Result<Credential, Box<dyn Error>> {
+    let body = oauth1_post(
+        ACCESS_TOKEN_URL,
+        consumer,
+        Some(request_token),
+        &[("oauth_verifier", pin)],
+    )?;
+    parse_token_response(&body)
+}
+
+/// Parses a `key=value&key=value` formatted OAuth token response into a
+/// `Credential`.
+fn parse_token_response(body: &str) -> Result<Credential, Box<dyn Error>> {
+    let mut key = None;
+    let mut secret = None;
+    for pair in body.split('&') {
+        let mut parts = pair.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some("oauth_token"), Some(value)) => key = Some(value.to_string()),
+            (Some("oauth_token_secret"), Some(value)) => secret = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    match (key, secret) {
+        (Some(key), Some(secret)) => Ok(Credential::new(&key, &secret)),
+        _ => Err(format!("Unexpected OAuth token response: {}", body).into()),
+    }
+}
+
+/// Signs and POSTs an OAuth 1.0a request, returning the response body.
+///
+/// `token` is the request token on the access-token exchange, and `None`
+/// when fetching the initial request token.
+fn oauth1_post(
+    url: &str,
+    consumer: &Credential,
+    token: Option<&Credential>,
+    extra_params: &[(&str, &str)],
+) -> Result<String, Box<dyn Error>> {
+    let mut params: BTreeMap<String, String> = BTreeMap::new();
+    params.insert("oauth_consumer_key".into(), consumer.key.clone());
+    params.insert("oauth_nonce".into(), generate_nonce());
+    params.insert("oauth_signature_method".into(), "HMAC-SHA1".into());
+    params.insert("oauth_timestamp".into(), current_unix_timestamp());
+    params.insert("oauth_version".into(), "1.0".into());
+    if let Some(token) = token {
+        params.insert("oauth_token".into(), token.key.clone());
+    }
+    for (key, value) in extra_params {
+        params.insert((*key).into(), (*value).into());
+    }
+
+    let token_secret = token.map(|t| t.secret.as_str()).unwrap_or("");
+    let signature = sign(url, &params, &consumer.secret, token_secret);
+    params.insert("oauth_signature".into(), signature);
+
+    let authorization_header = format!(
+        "OAuth {}",
+        params
+            .iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key, percent_encode(value)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let response = ureq::post(url)
+        .set("Authorization", &authorization_header)
+        .call()?;
+    Ok(response.into_string()?)
+}
+
+/// Computes the `HMAC-SHA1` OAuth 1.0a signature for a `POST` request whose
+/// only parameters are OAuth parameters (no query string or form body).
+fn sign(
+    url: &str,
+    params: &BTreeMap<String, String>,
+    consumer_secret: &str,
+    token_secret: &str,
+) -> String {
+    let param_string = params
+        .iter()
+        .map(|(key, value)| format!("{}={}", percent_encode(key), percent_encode(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let base_string = format!(
+        "POST&{}&{}",
+        percent_encode(url),
+        percent_encode(&param_string)
+    );
+    let signing_key = format!(
+        "{}&{}",
+        percent_encode(consumer_secret),
+        percent_encode(token_secret)
+    );
+
+    let mut mac = Hmac::<Sha1>::new_from_slice(signing_key.as_bytes())
+        .expect("HMAC can take a key of any size");
+    mac.update(base_string.as_bytes());
+    base64::encode(mac.finalize().into_bytes())
+}
+
+fn percent_encode(value: &str) -> String {
+    const ENCODE_SET: percent_encoding::AsciiSet = percent_encoding::NON_ALPHANUMERIC
+        .remove(b'-')
+        .remove(b'.')
+        .remove(b'_')
+        .remove(b'~');
+    percent_encoding::utf8_percent_encode(value, &ENCODE_SET).to_string()
+}
+
+fn generate_nonce() -> String {
+    use rand::Rng;
+    rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+fn current_unix_timestamp() -> String {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the Unix epoch")
+        .as_secs()
+        .to_string()
+}
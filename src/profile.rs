@@ -0,0 +1,73 @@
+use std::error::Error;
+use std::fmt;
+
+/// Name of the profile used when none is explicitly selected.
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
+/// A profile name that isn't safe to use as a single path segment under the
+/// cache root.
+#[derive(Debug)]
+pub enum ProfileNameError {
+    Invalid(String),
+}
+
+impl Error for ProfileNameError {}
+impl fmt::Display for ProfileNameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Identifies a named Twitter account whose cache data (the `UserIdLookup`
+/// and liked-tweet dumps) is kept in its own subdirectory, so a user
+/// archiving several accounts doesn't have one account's files collide with
+/// another's.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    name: String,
+}
+
+impl Profile {
+    /// Creates a profile with the given name.
+    ///
+    /// # Errors
+    ///
+    /// `name` is joined directly onto the resolved cache directory as a
+    /// single path segment, so this rejects anything that could escape that
+    /// directory: empty names, `.`/`..`, and names containing a path
+    /// separator.
+    pub fn new(name: &str) -> Result<Self, ProfileNameError> {
+        let is_valid = !name.is_empty()
+            && name != "."
+            && name != ".."
+            && !name.contains('/')
+            && !name.contains('\\');
+
+        if !is_valid {
+            return Err(ProfileNameError::Invalid(name.to_string()));
+        }
+
+        Ok(Profile {
+            name: name.to_string(),
+        })
+    }
+
+    /// Returns the profile's name, used as its cache subdirectory.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Default for Profile {
+    /// Profiles default to [`DEFAULT_PROFILE_NAME`] so existing single-account
+    /// usage keeps working without naming a profile explicitly.
+    fn default() -> Self {
+        Profile::new(DEFAULT_PROFILE_NAME).expect("DEFAULT_PROFILE_NAME is always a valid profile name")
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
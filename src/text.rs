@@ -0,0 +1,74 @@
+use crate::twitter::json_types::{TwitLikeDatum, UrlEntity};
+
+/// Reconstructs the full, human-readable text of a liked tweet.
+///
+/// Retweets carry their own truncated `text`/`full_text`, so this recurses
+/// into `retweeted_status` and uses the original tweet's text instead.
+/// Truncated tweets store their untruncated text under `extended_tweet`.
+/// Once the best available text is picked, HTML entities left encoded by
+/// the API are decoded and `t.co` short links are expanded back to their
+/// `expanded_url`.
+pub fn normalize_tweet_text(datum: &TwitLikeDatum) -> String {
+    if let Some(retweeted) = &datum.retweeted_status {
+        return normalize_tweet_text(retweeted);
+    }
+
+    let raw_text = if datum.truncated {
+        datum
+            .extended_tweet
+            .as_ref()
+            .and_then(|extended| extended.full_text.clone())
+            .or_else(|| datum.full_text.clone())
+            .unwrap_or_else(|| datum.text.clone())
+    } else {
+        datum
+            .full_text
+            .clone()
+            .unwrap_or_else(|| datum.text.clone())
+    };
+
+    expand_urls(&decode_html_entities(&raw_text), url_entities(datum))
+}
+
+/// Decodes the HTML entities the Twitter API leaves encoded in tweet text.
+///
+/// `&lt;`/`&gt;` must be decoded before `&amp;`: a literal `&lt;` in tweet
+/// text comes back from the API double-escaped as `&amp;lt;`, and decoding
+/// `&amp;` first would turn that into `&lt;`, which the next pass would then
+/// mangle into `<`.
+fn decode_html_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Picks the URL entities matching the text `normalize_tweet_text` chose.
+///
+/// The API's top-level `entities` only covers the legacy, truncated text;
+/// for a truncated tweet the rest of the (now full) text's URLs live under
+/// `extended_tweet.entities` instead, so t.co links past the truncation
+/// point would otherwise be left unexpanded.
+fn url_entities(datum: &TwitLikeDatum) -> &[UrlEntity] {
+    if datum.truncated {
+        if let Some(extended_urls) = datum
+            .extended_tweet
+            .as_ref()
+            .map(|extended| extended.entities.urls.as_slice())
+        {
+            if !extended_urls.is_empty() {
+                return extended_urls;
+            }
+        }
+    }
+    &datum.entities.urls
+}
+
+/// Replaces every `t.co` short URL in `text` with its expanded form, using
+/// the given URL entities as the source of truth.
+fn expand_urls(text: &str, url_entities: &[UrlEntity]) -> String {
+    let mut expanded = text.to_string();
+    for url_entity in url_entities {
+        expanded = expanded.replace(&url_entity.url, &url_entity.expanded_url);
+    }
+    expanded
+}